@@ -0,0 +1,143 @@
+use crate::group::Group;
+use crate::math::{mod_inverse, mod_sqrt};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+// https://eips.ethereum.org/EIPS/eip-2494
+const BABYJUBJUB_P: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+const BABYJUBJUB_A: u64 = 168700;
+const BABYJUBJUB_D: u64 = 168696;
+
+fn babyjubjub_modulus() -> BigUint {
+    BigUint::parse_bytes(BABYJUBJUB_P.as_bytes(), 10).unwrap()
+}
+
+fn invert(a: &BigUint, p: &BigUint) -> BigUint {
+    mod_inverse(a, p).expect("value is not invertible mod the curve's field modulus")
+}
+
+fn is_odd(n: &BigUint) -> bool {
+    n % BigUint::from(2u32) == BigUint::one()
+}
+
+/// A point on the BabyJubJub twisted Edwards curve
+/// `a*x^2 + y^2 = 1 + d*x^2*y^2 (mod p)`, used as a [`Group`] so the generic
+/// BSGS core can solve elliptic-curve discrete logs the same way it solves
+/// logs over [`crate::ModP`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BabyJubJubPoint {
+    pub x: BigUint,
+    pub y: BigUint,
+}
+
+impl BabyJubJubPoint {
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        let p = babyjubjub_modulus();
+        Self {
+            x: x % &p,
+            y: y % &p,
+        }
+    }
+}
+
+impl Group for BabyJubJubPoint {
+    fn identity(&self) -> Self {
+        Self {
+            x: BigUint::zero(),
+            y: BigUint::one(),
+        }
+    }
+
+    fn op(&self, other: &Self) -> Self {
+        let p = babyjubjub_modulus();
+        let a = BigUint::from(BABYJUBJUB_A);
+        let d = BigUint::from(BABYJUBJUB_D);
+
+        let x1y2 = (&self.x * &other.y) % &p;
+        let y1x2 = (&self.y * &other.x) % &p;
+        let y1y2 = (&self.y * &other.y) % &p;
+        let x1x2 = (&self.x * &other.x) % &p;
+        let dx1x2y1y2 = (&d * &x1x2 * &y1y2) % &p;
+
+        let x3_num = (&x1y2 + &y1x2) % &p;
+        let x3_den = (BigUint::one() + &dx1x2y1y2) % &p;
+        let x3 = (&x3_num * invert(&x3_den, &p)) % &p;
+
+        let y3_num = (&p + &y1y2 - (&a * &x1x2) % &p) % &p;
+        let y3_den = (&p + BigUint::one() - &dx1x2y1y2) % &p;
+        let y3 = (&y3_num * invert(&y3_den, &p)) % &p;
+
+        Self { x: x3, y: y3 }
+    }
+
+    fn inverse(&self) -> Self {
+        let p = babyjubjub_modulus();
+        Self {
+            x: (&p - &self.x) % &p,
+            y: self.y.clone(),
+        }
+    }
+
+    fn pow(&self, exponent: &BigUint) -> Self {
+        // Double-and-add scalar multiplication.
+        let mut result = self.identity();
+        let mut base = self.clone();
+        let mut e = exponent.clone();
+        while !e.is_zero() {
+            if &e % BigUint::from(2u32) == BigUint::one() {
+                result = result.op(&base);
+            }
+            base = base.op(&base);
+            e /= BigUint::from(2u32);
+        }
+        result
+    }
+}
+
+/// Compresses a point to its 32-byte little-endian `y` coordinate, with
+/// `x`'s sign packed into the otherwise-unused top bit (BabyJubJub's field
+/// modulus is under 2^254, so that bit is always free).
+pub fn compress_point(point: &BabyJubJubPoint) -> [u8; 32] {
+    let mut bytes = point.y.to_bytes_le();
+    bytes.resize(32, 0);
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&bytes);
+    if is_odd(&point.x) {
+        out[31] |= 0x80;
+    }
+    out
+}
+
+/// Reconstructs a point from its compressed form: recovers `x` from the
+/// curve equation `a*x^2 + y^2 = 1 + d*x^2*y^2` solved for `x^2`, takes its
+/// modular square root, then picks the root matching the packed sign bit.
+/// Returns `None` if the bytes don't encode a valid curve point.
+pub fn decompress_point(compressed: &[u8; 32]) -> Option<BabyJubJubPoint> {
+    let mut bytes = *compressed;
+    let x_is_odd = (bytes[31] & 0x80) != 0;
+    bytes[31] &= 0x7f;
+
+    let p = babyjubjub_modulus();
+    let y = BigUint::from_bytes_le(&bytes);
+    if y >= p {
+        return None;
+    }
+
+    let a = BigUint::from(BABYJUBJUB_A);
+    let d = BigUint::from(BABYJUBJUB_D);
+    let y2 = (&y * &y) % &p;
+
+    let numerator = (&p + BigUint::one() - &y2) % &p;
+    let denominator = (&p + &a - (&d * &y2) % &p) % &p;
+    let x2 = (&numerator * invert(&denominator, &p)) % &p;
+
+    let root = mod_sqrt(&x2, &p)?;
+    let x = if is_odd(&root) == x_is_odd {
+        root
+    } else {
+        (&p - &root) % &p
+    };
+
+    Some(BabyJubJubPoint { x, y })
+}
@@ -0,0 +1,103 @@
+use crate::math::mod_inverse;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A finite group element that the generic BSGS core can search over.
+///
+/// `identity` takes `&self` (rather than being a bare associated function)
+/// because groups like [`ModP`](crate::ModP) carry runtime parameters
+/// (the modulus, curve constants, ...) that an element instance already
+/// holds but the bare `Self` type does not.
+pub trait Group {
+    fn identity(&self) -> Self;
+    fn op(&self, other: &Self) -> Self;
+    fn inverse(&self) -> Self;
+    fn pow(&self, exponent: &BigUint) -> Self;
+}
+
+/// Generic baby-step giant-step core: solves `h = g^x` for `x` in any finite
+/// group implementing [`Group`], given the order of `g` within that group.
+///
+/// This is the same algorithm as `BSGS::run`, lifted off `BigUint`/`modpow`
+/// so it also works over elliptic-curve points and other groups.
+pub fn run<G: Group + Eq + Hash + Clone>(g: &G, h: &G, order: &BigUint) -> Option<BigUint> {
+    let m = if order.is_zero() {
+        BigUint::one()
+    } else {
+        (order - BigUint::one()).sqrt() + BigUint::one()
+    };
+
+    let mut lookup_table: HashMap<G, BigUint> = HashMap::new();
+    let mut current = g.identity();
+    let mut j = BigUint::zero();
+    while j < m {
+        lookup_table.insert(current.clone(), j.clone());
+        current = current.op(g);
+        j += BigUint::one();
+    }
+
+    // Giant Step pre-computation: c = g^(-m)
+    let c = g.pow(&m).inverse();
+
+    // Giant Steps
+    let mut gamma = h.clone();
+    let mut i = BigUint::zero();
+    while i < m {
+        if let Some(j) = lookup_table.get(&gamma) {
+            return Some(&i * &m + j);
+        }
+        gamma = gamma.op(&c);
+        i += BigUint::one();
+    }
+
+    None
+}
+
+/// The multiplicative group of integers modulo `modulus`, i.e. the group
+/// `BSGS::run` has always solved over, expressed as a [`Group`] impl.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModP {
+    pub value: BigUint,
+    pub modulus: BigUint,
+}
+
+impl ModP {
+    pub fn new(value: BigUint, modulus: BigUint) -> Self {
+        let value = &value % &modulus;
+        Self { value, modulus }
+    }
+}
+
+impl Group for ModP {
+    fn identity(&self) -> Self {
+        Self {
+            value: BigUint::one() % &self.modulus,
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    fn op(&self, other: &Self) -> Self {
+        Self {
+            value: (&self.value * &other.value) % &self.modulus,
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    fn inverse(&self) -> Self {
+        let value = mod_inverse(&self.value, &self.modulus)
+            .expect("value is not invertible mod the given modulus");
+        Self {
+            value,
+            modulus: self.modulus.clone(),
+        }
+    }
+
+    fn pow(&self, exponent: &BigUint) -> Self {
+        Self {
+            value: self.value.modpow(exponent, &self.modulus),
+            modulus: self.modulus.clone(),
+        }
+    }
+}
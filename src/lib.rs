@@ -6,11 +6,17 @@ use num_bigint::BigUint;
 pub struct BSGS(String);
 pub struct Parallel(String);
 
+mod group;
+mod curve;
+mod math;
+pub use group::{Group, ModP};
+pub use curve::{compress_point, decompress_point, BabyJubJubPoint};
+pub use math::{mod_inverse, mod_sqrt};
 
 use rayon::prelude::*;
 use num_traits::{One, Zero};
 use std::collections::HashMap;
-use std::sync::{Mutex, Arc};
+use dashmap::DashMap;
 // https://github.com/ashutosh1206/Crypton/blob/master/Discrete-Logarithm-Problem/Algo-Baby-Step-Giant-Step/bsgs.py
 
 
@@ -30,6 +36,9 @@ impl BSGS {
     //             Result of g**x % p
     //     p : int/long
     //             Group over which DLP is generated. Commonly p is a prime number
+    //     order : int/long
+    //             True order of g, which may differ from p - 1 for composite
+    //             moduli or when g generates a proper subgroup
 
     // :variables:
     //     m : int/long
@@ -49,100 +58,193 @@ impl BSGS {
     
 
 
-    pub fn run(g: &BigUint, h: &BigUint, p: &BigUint) -> Option<BigUint>  {
+    pub fn run(g: &BigUint, h: &BigUint, p: &BigUint, order: &BigUint) -> Option<BigUint>  {
         let mod_size = p.bits();
 
         println!("[+] Using BSGS algorithm to solve DLP");
         println!("[+] Modulus size: {}. Warning! BSGS not space efficient\n", mod_size);
-    
-        let m = (*&p - BigUint::one()).sqrt() + BigUint::one();
+
+        // Delegates to the generic core over the multiplicative group mod p.
+        let g_elem = ModP::new(g.clone(), p.clone());
+        let h_elem = ModP::new(h.clone(), p.clone());
+        group::run(&g_elem, &h_elem, order)
+    }
+}
+
+impl Parallel  {
+    /// Same DLP as `BSGS::run`, but fills the baby-step table and searches
+    /// the giant steps concurrently.
+    ///
+    /// The baby step fills a sharded `DashMap` instead of a
+    /// `Mutex<HashMap>`, so inserts across threads don't serialize on a
+    /// single global lock. The giant step runs as a parallel search that
+    /// stops as soon as any worker finds a match, via `find_map_any`.
+    /// `num_threads` caps parallelism; `None` uses every core.
+    pub fn run(
+        g: &BigUint,
+        h: &BigUint,
+        p: &BigUint,
+        order: &BigUint,
+        num_threads: Option<usize>,
+    ) -> Option<BigUint>  {
+        let mod_size = p.bits();
+
+        println!("[+] Using BSGS algorithm to solve DLP");
+        println!("[+] Modulus size: {}. Warning! BSGS not space efficient\n", mod_size);
+
+        let m = (order.clone() - BigUint::one()).sqrt() + BigUint::one();
+        let m_usize = usize_from_biguint(&m);
+
+        let num_threads = num_threads.unwrap_or_else(num_cpus::get);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .expect("failed to build BSGS thread pool");
+
+        pool.install(|| {
+            // Baby Step: every thread inserts straight into the shared map;
+            // DashMap shards its locking internally instead of using one
+            // lock for the whole table.
+            let lookup_table: DashMap<BigUint, BigUint> = DashMap::new();
+            (0..m_usize).into_par_iter().for_each(|j_usize| {
+                let j = BigUint::from(j_usize);
+                let key = g.modpow(&j, p);
+                lookup_table.insert(key, j);
+            });
+
+            // Giant Step pre-computation
+            let c = mod_inverse(&g.modpow(&m, p), p)
+                .expect("g^m is not invertible mod p");
+
+            // Giant Steps: search in parallel, returning as soon as any
+            // worker finds a match instead of scanning the rest.
+            (0..m_usize).into_par_iter().find_map_any(|i_usize| {
+                let i = BigUint::from(i_usize);
+                let temp = (h * c.modpow(&i, p)) % p;
+                lookup_table.get(&temp).map(|j| i * &m + j.clone())
+            })
+        })
+    }
+}
+
+/// Solves many discrete logs sharing the same generator `g` and modulus `p`
+/// over a known, bounded exponent range `[0, bound)` (e.g. batch-decrypting
+/// a stream of ElGamal-style ciphertexts).
+///
+/// Unlike `BSGS::run`, the baby-step `lookup_table` is built once in `new`
+/// and reused by every call to `solve`/`solve_many`, and the exponent bound
+/// `B` (rather than `p`) sizes the table: `m = ceil(sqrt(B))`.
+pub struct BatchBSGS {
+    g: BigUint,
+    p: BigUint,
+    m: BigUint,
+    // Giant Step pre-computation: c = g^(-m) % p, computed once here instead
+    // of on every `solve` call (`solve_many` uses its own batched inverses).
+    c: BigUint,
+    lookup_table: HashMap<BigUint, BigUint>,
+}
+
+impl BatchBSGS {
+    pub fn new(g: &BigUint, p: &BigUint, bound: &BigUint) -> Self {
+        let m = (bound - BigUint::one()).sqrt() + BigUint::one();
+
         let mut lookup_table: HashMap<BigUint, BigUint> = HashMap::new();
-    
-        // Baby Step
         let mut j = BigUint::zero();
-        while &j < &m {
-            let key = g.modpow(&j, &p);
-            lookup_table.insert(key.clone(), j.clone());
+        while j < m {
+            let key = g.modpow(&j, p);
+            lookup_table.insert(key, j.clone());
             j += BigUint::one();
         }
 
-    
-        // Giant Step pre-computation
-        let c = g.modpow(&(&m * (*&p - BigUint::from(2u32))), &p);
-    
-        // Giant Steps
+        let c = mod_inverse(&g.modpow(&m, p), p).expect("g^m is not invertible mod p");
+
+        Self {
+            g: g.clone(),
+            p: p.clone(),
+            m,
+            c,
+            lookup_table,
+        }
+    }
+
+    /// Solves a single instance `h = g^x % p` against the precomputed table.
+    pub fn solve(&self, h: &BigUint) -> Option<BigUint> {
         let mut i = BigUint::zero();
-        while &i < &m {
-            let temp = &(h * &c.modpow(&i, &p)) % p;
-            if let Some(j) = lookup_table.get(&temp) {
-                // x found
-                return Some(i * &m + j);
+        while i < self.m {
+            let temp = &(h * &self.c.modpow(&i, &self.p)) % &self.p;
+            if let Some(j) = self.lookup_table.get(&temp) {
+                return Some(i * &self.m + j);
             }
             i += BigUint::one();
         }
-    
+
         None
     }
-}
 
-impl Parallel  {
-    fn run(g: &BigUint, h: &BigUint, p: &BigUint) -> Option<BigUint>  {
-        let mod_size = p.bits();
-
-        println!("[+] Using BSGS algorithm to solve DLP");
-        println!("[+] Modulus size: {}. Warning! BSGS not space efficient\n", mod_size);
+    /// Solves many instances against the same precomputed table, batching
+    /// the giant-step modular inversions with Montgomery's trick instead of
+    /// inverting `g^(-m)` separately for every instance.
+    ///
+    /// The giant-step accumulators `a_i = g^(i*m) % p` for `i = 1..=m` are
+    /// built by repeated multiplication (no inversion needed), then their
+    /// prefix products `pre[i] = a_1*...*a_i % p` are formed, `pre[m]` is
+    /// inverted once, and the individual inverses are recovered walking
+    /// backwards: `inv(a_i) = pre[i-1] * acc`, `acc = acc * a_i`. This turns
+    /// `m` modular inversions into a single inversion plus ~3m multiplications.
+    pub fn solve_many(&self, hs: &[BigUint]) -> Vec<Option<BigUint>> {
+        let m_usize = usize_from_biguint(&self.m);
+
+        let a1 = self.g.modpow(&self.m, &self.p);
+        let mut accumulators: Vec<BigUint> = Vec::with_capacity(m_usize);
+        let mut acc = BigUint::one();
+        for _ in 0..m_usize {
+            acc = (&acc * &a1) % &self.p;
+            accumulators.push(acc.clone());
+        }
 
-        let m = (p.clone() - BigUint::one()).sqrt() + BigUint::one();
+        // Prefix products pre[i] = a_1 * ... * a_i % p, pre[0] = 1.
+        let mut prefix: Vec<BigUint> = Vec::with_capacity(m_usize + 1);
+        prefix.push(BigUint::one());
+        for a in &accumulators {
+            let last = prefix.last().unwrap().clone();
+            prefix.push((&last * a) % &self.p);
+        }
 
+        // Single inversion of the full product.
+        let total = prefix.last().unwrap().clone();
+        let mut running_inv = mod_inverse(&total, &self.p).expect("accumulator is not invertible mod p");
 
-        let num_threads = num_cpus::get();
-        let chunk_size = m.clone()/num_threads;
-        let lookup_table: Arc<Mutex<HashMap<BigUint, BigUint>>> = Arc::new(Mutex::new(HashMap::new()));
+        let mut inverses = vec![BigUint::zero(); m_usize];
+        for i in (0..m_usize).rev() {
+            inverses[i] = (&prefix[i] * &running_inv) % &self.p;
+            running_inv = (&running_inv * &accumulators[i]) % &self.p;
+        }
 
-        (0..num_threads).into_par_iter().for_each(|thread_num| {
-            let start = thread_num * &chunk_size;
-            let clone = start.clone();
-            let end = if thread_num == (num_threads - 1) {
-                m.clone()
-            } else {
-                start + &chunk_size
-            };
-    
-            let mut j = clone;
-            
-            while j < end {
-                let key = g.modpow(&j, &p);
-                // Lock the mutex to access and update the shared lookup table
-                let mut locked_table = lookup_table.lock().unwrap();
-                locked_table.insert(key.clone(), j.clone());
-                let jbis = j.clone();
-                j = jbis + BigUint::one();
-            }
-        });
-    
-            // Continue with the Giant Step pre-computation and Giant Steps as before
-    
-            let c = g.modpow(&(m.clone() * (*&p - BigUint::from(2u32))), &p);
-    
-            let mut i = BigUint::zero();
-            while &i < &m {
-                let temp = &(h * &c.modpow(&i, &p)) % p;
-        
-                // Lock the mutex to access the shared lookup table
-                let locked_table = lookup_table.lock().unwrap();
-                if let Some(j) = locked_table.get(&temp) {
-                    // x found
-                    return Some(i.clone() * m.clone() + j.clone());
+        hs.iter()
+            .map(|h| {
+                // i' = 0 first, as `solve` starts at i = 0: otherwise a
+                // direct hit here can collide with a wrapped-around match at
+                // some i' >= 1, returning x > bound.
+                if let Some(j) = self.lookup_table.get(h) {
+                    return Some(j.clone());
                 }
-        
-                i = &i + BigUint::one();
-            }
-
-            None
-
+                for (i, inv) in inverses.iter().enumerate() {
+                    let temp = (h * inv) % &self.p;
+                    if let Some(j) = self.lookup_table.get(&temp) {
+                        let i_big = BigUint::from(i + 1);
+                        return Some(i_big * &self.m + j);
+                    }
+                }
+                None
+            })
+            .collect()
     }
 }
 
+fn usize_from_biguint(n: &BigUint) -> usize {
+    n.to_string().parse().expect("bound too large to size a giant-step table")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,7 +257,8 @@ mod tests {
         let g = BigUint::parse_bytes(b"2", 10).unwrap();
         let h = BigUint::parse_bytes(b"4178319614", 10).unwrap();
         let p = BigUint::parse_bytes(b"6971096459", 10).unwrap();
-        let result = BSGS::run(&g, &h, &p);
+        let order = &p - BigUint::one();
+        let result = BSGS::run(&g, &h, &p, &order);
         let c =  g.modpow(&result.unwrap(), &p);
         assert_eq!(c, answer);
     }
@@ -166,7 +269,8 @@ mod tests {
         let g = BigUint::parse_bytes(b"3", 10).unwrap();
         let h = BigUint::parse_bytes(b"362073897", 10).unwrap();
         let p = BigUint::parse_bytes(b"2500000001", 10).unwrap();
-        let result = BSGS::run(&g, &h, &p);
+        let order = &p - BigUint::one();
+        let result = BSGS::run(&g, &h, &p, &order);
         let c =  g.modpow(&result.unwrap(), &p);
         assert_eq!(c, answer);
     }
@@ -180,7 +284,8 @@ mod tests {
         let g = BigUint::parse_bytes(b"3", 10).unwrap();
         let h = BigUint::parse_bytes(b"362073897", 10).unwrap();
         let p = BigUint::parse_bytes(b"2500000001", 10).unwrap();
-        let result = Parallel::run(&g, &h, &p);
+        let order = &p - BigUint::one();
+        let result = Parallel::run(&g, &h, &p, &order, None);
         let c =  g.modpow(&result.unwrap(), &p);
         assert_eq!(c, answer);
     }
@@ -192,8 +297,129 @@ mod tests {
         let g = BigUint::parse_bytes(b"2", 10).unwrap();
         let h = BigUint::parse_bytes(b"4178319614", 10).unwrap();
         let p = BigUint::parse_bytes(b"6971096459", 10).unwrap();
-        let result = Parallel::run(&g, &h, &p);
+        let order = &p - BigUint::one();
+        let result = Parallel::run(&g, &h, &p, &order, None);
         let c =  g.modpow(&result.unwrap(), &p);
         assert_eq!(c, answer);
     }
+
+    #[test]
+    fn batch_bsgs_solve_matches_single_run() {
+        let g = BigUint::parse_bytes(b"2", 10).unwrap();
+        let p = BigUint::parse_bytes(b"6971096459", 10).unwrap();
+        let bound = BigUint::parse_bytes(b"6971096459", 10).unwrap();
+        let h = BigUint::parse_bytes(b"4178319614", 10).unwrap();
+
+        let batch = BatchBSGS::new(&g, &p, &bound);
+        let result = batch.solve(&h).unwrap();
+        assert_eq!(g.modpow(&result, &p), h);
+    }
+
+    #[test]
+    fn batch_bsgs_solve_many_matches_solve() {
+        let g = BigUint::parse_bytes(b"2", 10).unwrap();
+        let p = BigUint::parse_bytes(b"6971096459", 10).unwrap();
+        let bound = BigUint::parse_bytes(b"6971096459", 10).unwrap();
+        let hs = vec![
+            BigUint::parse_bytes(b"4178319614", 10).unwrap(),
+            g.modpow(&BigUint::from(12345u32), &p),
+            g.modpow(&BigUint::from(999999u32), &p),
+        ];
+
+        let batch = BatchBSGS::new(&g, &p, &bound);
+        let many = batch.solve_many(&hs);
+        for (h, found) in hs.iter().zip(many.iter()) {
+            let x = found.clone().unwrap();
+            assert_eq!(&g.modpow(&x, &p), h);
+            assert_eq!(Some(x), batch.solve(h));
+        }
+    }
+
+    #[test]
+    fn batch_bsgs_solve_many_does_not_wrap_around_group_order() {
+        // g's order is p - 1 here, so a direct i' = 0 hit for h = g^777 also
+        // collides with a wrapped-around giant-step match at some i' >= 1;
+        // solve_many must return the direct, in-bound answer (777), not the
+        // wrapped one (777 + (p - 1)), matching `solve` and staying < bound.
+        let g = BigUint::parse_bytes(b"2", 10).unwrap();
+        let p = BigUint::parse_bytes(b"6971096459", 10).unwrap();
+        let bound = p.clone();
+        let h = g.modpow(&BigUint::from(777u32), &p);
+
+        let batch = BatchBSGS::new(&g, &p, &bound);
+        let result = batch.solve_many(std::slice::from_ref(&h))[0].clone().unwrap();
+
+        assert_eq!(result, BigUint::from(777u32));
+        assert_eq!(Some(result), batch.solve(&h));
+    }
+
+    #[test]
+    fn mod_inverse_recovers_fermat_inverse_for_prime_modulus() {
+        let a = BigUint::from(17u32);
+        let p = BigUint::from(6971096459u64);
+        let inv = mod_inverse(&a, &p).unwrap();
+        assert_eq!((&a * &inv) % &p, BigUint::one());
+    }
+
+    #[test]
+    fn mod_inverse_none_for_non_coprime_composite_modulus() {
+        // gcd(4, 8) = 4, so 4 has no inverse mod 8.
+        assert_eq!(mod_inverse(&BigUint::from(4u32), &BigUint::from(8u32)), None);
+    }
+
+    #[test]
+    fn generic_run_solves_babyjubjub_point_dlp() {
+        // Small scalar so the giant step loop terminates quickly in a test.
+        let g = BabyJubJubPoint::new(
+            BigUint::parse_bytes(
+                b"995203441582195749578291179787384436505546430278305826713579947235728471134",
+                10,
+            )
+            .unwrap(),
+            BigUint::parse_bytes(
+                b"5472060717959818805561601436314318772137091100104008585924551046643952123905",
+                10,
+            )
+            .unwrap(),
+        );
+        let x = BigUint::from(1234u32);
+        let h = g.pow(&x);
+
+        let result = group::run(&g, &h, &BigUint::from(10_000u32)).unwrap();
+        assert_eq!(g.pow(&result), h);
+    }
+
+    #[test]
+    fn mod_sqrt_root_squares_back_to_input() {
+        let p = BigUint::from(6971096459u64);
+        let a = BigUint::from(16u32);
+        let root = mod_sqrt(&a, &p).unwrap();
+        assert_eq!((&root * &root) % &p, a);
+    }
+
+    #[test]
+    fn mod_sqrt_none_for_quadratic_non_residue() {
+        // 3 is a non-residue mod 7 (the residues mod 7 are {0, 1, 2, 4}).
+        assert_eq!(mod_sqrt(&BigUint::from(3u32), &BigUint::from(7u32)), None);
+    }
+
+    #[test]
+    fn compress_decompress_point_round_trip() {
+        let g = BabyJubJubPoint::new(
+            BigUint::parse_bytes(
+                b"995203441582195749578291179787384436505546430278305826713579947235728471134",
+                10,
+            )
+            .unwrap(),
+            BigUint::parse_bytes(
+                b"5472060717959818805561601436314318772137091100104008585924551046643952123905",
+                10,
+            )
+            .unwrap(),
+        );
+
+        let compressed = compress_point(&g);
+        let decompressed = decompress_point(&compressed).unwrap();
+        assert_eq!(decompressed, g);
+    }
 }
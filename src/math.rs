@@ -0,0 +1,100 @@
+use num_bigint::{BigInt, BigUint};
+use num_traits::{One, Zero};
+
+/// Modular inverse of `a` mod `n` via the extended Euclidean algorithm.
+///
+/// Unlike `a^(n-2) mod n` (Fermat's little theorem), this is correct for any
+/// `n`, not just primes: it tracks `(old_r, r)` and `(old_s, s)` through the
+/// Euclidean algorithm on `(a, n)`, and at termination `old_r` is `gcd(a, n)`.
+/// The inverse exists iff `old_r == 1`; otherwise `None` is returned.
+pub fn mod_inverse(a: &BigUint, n: &BigUint) -> Option<BigUint> {
+    let n_int = BigInt::from(n.clone());
+
+    let (mut old_r, mut r) = (BigInt::from(a.clone()), n_int.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+
+    while !r.is_zero() {
+        let quotient = &old_r / &r;
+
+        let new_r = &old_r - &quotient * &r;
+        old_r = r;
+        r = new_r;
+
+        let new_s = &old_s - &quotient * &s;
+        old_s = s;
+        s = new_s;
+    }
+
+    if old_r != BigInt::one() {
+        return None;
+    }
+
+    let inverse = ((old_s % &n_int) + &n_int) % &n_int;
+    inverse.to_biguint()
+}
+
+/// Modular square root of `a` mod an odd prime `p` via Tonelli-Shanks.
+///
+/// Returns `None` if `a` is a quadratic non-residue mod `p` (checked via
+/// Euler's criterion `a^((p-1)/2) == 1`). Used to reconstruct a curve
+/// point's `y` (or `x`) coordinate from the other coordinate plus a sign
+/// bit, i.e. point decompression.
+pub fn mod_sqrt(a: &BigUint, p: &BigUint) -> Option<BigUint> {
+    let one = BigUint::one();
+    let two = BigUint::from(2u32);
+
+    let a = a % p;
+    if a.is_zero() {
+        return Some(BigUint::zero());
+    }
+
+    let euler_exponent = (p - &one) / &two;
+    if a.modpow(&euler_exponent, p) != one {
+        return None;
+    }
+
+    // p - 1 = q * 2^s, q odd.
+    let mut q = p - &one;
+    let mut s = 0u32;
+    while (&q % &two).is_zero() {
+        q /= &two;
+        s += 1;
+    }
+
+    // Find a quadratic non-residue z via Euler's criterion: z^((p-1)/2) == -1.
+    let p_minus_one = p - &one;
+    let mut candidate = two.clone();
+    let z = loop {
+        if candidate.modpow(&euler_exponent, p) == p_minus_one {
+            break candidate;
+        }
+        candidate += &one;
+    };
+
+    let mut m = s;
+    let mut c = z.modpow(&q, p);
+    let mut t = a.modpow(&q, p);
+    let mut r = a.modpow(&((&q + &one) / &two), p);
+
+    while t != one {
+        // Least i, 0 < i < m, such that t^(2^i) == 1.
+        let mut i = 0u32;
+        let mut t2i = t.clone();
+        while t2i != one {
+            t2i = (&t2i * &t2i) % p;
+            i += 1;
+        }
+
+        let mut b = c.clone();
+        for _ in 0..(m - i - 1) {
+            b = (&b * &b) % p;
+        }
+
+        m = i;
+        c = (&b * &b) % p;
+        t = (&t * &c) % p;
+        r = (&r * &b) % p;
+    }
+
+    Some(r)
+}